@@ -0,0 +1,111 @@
+use crate::{ua, AsyncClient, Error};
+
+/// Optional correlation metadata attached to a single [`Op`] in an [`AsyncClient::batch()`] call.
+///
+/// `open62541` never interprets the header; it is simply handed back unchanged on the matching
+/// [`OpResult`], which is useful for correlating responses with requests once operations may have
+/// completed out of submission order internally (even though `batch()` itself always returns
+/// results in submission order).
+pub type Header = Option<String>;
+
+/// A single operation to run as part of an [`AsyncClient::batch()`] call.
+pub enum Op {
+    /// Reads the value of a single node.
+    Read {
+        node_id: ua::NodeId,
+        header: Header,
+    },
+    /// Writes the value of a single node.
+    Write {
+        node_id: ua::NodeId,
+        value: ua::DataValue,
+        header: Header,
+    },
+    /// Deletes the given monitored items.
+    DeleteMonitoredItems {
+        request: ua::DeleteMonitoredItemsRequest,
+        header: Header,
+    },
+}
+
+/// Result of a single [`Op`] run as part of an [`AsyncClient::batch()`] call.
+pub enum OpResult {
+    /// Result of [`Op::Read`].
+    Read {
+        value: ua::DataValue,
+        header: Header,
+    },
+    /// Result of [`Op::Write`].
+    Write {
+        header: Header,
+    },
+    /// Result of [`Op::DeleteMonitoredItems`].
+    DeleteMonitoredItems {
+        response: ua::DeleteMonitoredItemsResponse,
+        header: Header,
+    },
+}
+
+/// Options for [`AsyncClient::batch()`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BatchOptions {
+    sequential: bool,
+}
+
+impl BatchOptions {
+    /// Forces operations to run one after another, in submission order.
+    ///
+    /// By default, operations are dispatched concurrently; [`AsyncClient::batch()`] always
+    /// returns results in submission order regardless of this setting. Enable this for servers
+    /// that cannot handle concurrent requests on a single session.
+    #[must_use]
+    pub fn sequential(mut self, sequential: bool) -> Self {
+        self.sequential = sequential;
+        self
+    }
+}
+
+async fn run_op(client: &AsyncClient, op: Op) -> Result<OpResult, Error> {
+    match op {
+        Op::Read { node_id, header } => client
+            .read_value(&node_id)
+            .await
+            .map(|value| OpResult::Read { value, header }),
+        Op::Write {
+            node_id,
+            value,
+            header,
+        } => client
+            .write_value(&node_id, &value)
+            .await
+            .map(|()| OpResult::Write { header }),
+        Op::DeleteMonitoredItems { request, header } => client
+            .delete_monitored_items(request)
+            .await
+            .map(|response| OpResult::DeleteMonitoredItems { response, header }),
+    }
+}
+
+impl AsyncClient {
+    /// Runs a heterogeneous batch of read, write, and delete-monitored-items operations, and
+    /// returns their results in submission order.
+    ///
+    /// By default, operations are polled concurrently on the current task (not spawned onto the
+    /// `tokio` runtime, so this does not require `AsyncClient` or the values held by `ops` to be
+    /// `Send`); use [`BatchOptions::sequential()`] to force them to run one after another instead.
+    /// Either way, the returned `Vec` preserves submission order, so results can be matched back
+    /// up to `ops` by index, and each result carries the `header` of the operation that produced
+    /// it for additional correlation.
+    pub async fn batch(&self, ops: Vec<Op>, options: BatchOptions) -> Vec<Result<OpResult, Error>> {
+        if options.sequential {
+            let mut results = Vec::with_capacity(ops.len());
+            for op in ops {
+                results.push(run_op(self, op).await);
+            }
+            return results;
+        }
+
+        let futures = ops.into_iter().map(|op| run_op(self, op));
+        futures::future::join_all(futures).await
+    }
+}
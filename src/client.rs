@@ -1,32 +1,242 @@
 use std::{
     ffi::{c_char, c_void, CStr, CString},
     ptr,
+    sync::Arc,
 };
 
-use log::{debug, error, info, trace, warn};
+use log::info;
 use open62541_sys::{
-    va_list, UA_ClientConfig, UA_ClientConfig_setDefault, UA_Client_connect, UA_Client_getConfig,
-    UA_LogCategory, UA_LogLevel, UA_LogLevel_UA_LOGLEVEL_DEBUG, UA_LogLevel_UA_LOGLEVEL_ERROR,
+    va_list, UA_AnonymousIdentityToken, UA_ByteString_clear, UA_ClientConfig,
+    UA_ClientConfig_setDefault, UA_ClientConfig_setDefaultEncryption, UA_Client_connect,
+    UA_Client_getConfig, UA_ExtensionObject_clear, UA_ExtensionObject_setValueCopy, UA_LogCategory,
+    UA_LogLevel, UA_LogLevel_UA_LOGLEVEL_DEBUG, UA_LogLevel_UA_LOGLEVEL_ERROR,
     UA_LogLevel_UA_LOGLEVEL_FATAL, UA_LogLevel_UA_LOGLEVEL_INFO, UA_LogLevel_UA_LOGLEVEL_TRACE,
-    UA_LogLevel_UA_LOGLEVEL_WARNING, UA_STATUSCODE_GOOD,
+    UA_LogLevel_UA_LOGLEVEL_WARNING, UA_MessageSecurityMode,
+    UA_MessageSecurityMode_UA_MESSAGESECURITYMODE_NONE,
+    UA_MessageSecurityMode_UA_MESSAGESECURITYMODE_SIGNANDENCRYPT, UA_STATUSCODE_GOOD,
+    UA_String_clear, UA_TYPES, UA_TYPES_ANONYMOUSIDENTITYTOKEN, UA_TYPES_USERNAMEIDENTITYTOKEN,
+    UA_TYPES_X509IDENTITYTOKEN, UA_UserNameIdentityToken, UA_UserNameIdentityToken_clear,
+    UA_X509IdentityToken, UA_X509IdentityToken_clear,
 };
 
 #[cfg(feature = "tokio")]
 use crate::AsyncClient;
-use crate::{ua, Error};
+use crate::{
+    log_buffer::{install_log_buffer, LogBuffer, LogRecord},
+    logger::LoggerConfig,
+    ua, Error,
+};
+
+/// Message security mode used to secure the connection to the server.
+///
+/// This selects whether messages exchanged with the server are signed and/or encrypted once a
+/// [`SecurityPolicy`] other than [`SecurityPolicy::None`] has been negotiated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SecurityMode {
+    /// Messages are neither signed nor encrypted.
+    None,
+    /// Messages are signed and encrypted.
+    SignAndEncrypt,
+}
+
+impl SecurityMode {
+    const fn as_inner(self) -> UA_MessageSecurityMode {
+        match self {
+            Self::None => UA_MessageSecurityMode_UA_MESSAGESECURITYMODE_NONE,
+            Self::SignAndEncrypt => UA_MessageSecurityMode_UA_MESSAGESECURITYMODE_SIGNANDENCRYPT,
+        }
+    }
+}
+
+/// Security policy used to secure the connection to the server.
+///
+/// See part 7 of the OPC UA specification for the meaning of the individual policies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SecurityPolicy {
+    /// No security policy, i.e. an unsecured connection.
+    None,
+    Basic128Rsa15,
+    Basic256,
+    Basic256Sha256,
+    Aes128Sha256RsaOaep,
+    Aes256Sha256RsaPss,
+}
+
+impl SecurityPolicy {
+    const fn uri(self) -> &'static str {
+        match self {
+            Self::None => "http://opcfoundation.org/UA/SecurityPolicy#None",
+            Self::Basic128Rsa15 => "http://opcfoundation.org/UA/SecurityPolicy#Basic128Rsa15",
+            Self::Basic256 => "http://opcfoundation.org/UA/SecurityPolicy#Basic256",
+            Self::Basic256Sha256 => "http://opcfoundation.org/UA/SecurityPolicy#Basic256Sha256",
+            Self::Aes128Sha256RsaOaep => {
+                "http://opcfoundation.org/UA/SecurityPolicy#Aes128Sha256RsaOaep"
+            }
+            Self::Aes256Sha256RsaPss => {
+                "http://opcfoundation.org/UA/SecurityPolicy#Aes256Sha256RsaPss"
+            }
+        }
+    }
+}
 
 /// Builder for [`Client`].
 ///
 /// Use this to specify additional options before connecting to an OPC UA endpoint.
 #[allow(clippy::module_name_repetitions)]
-pub struct ClientBuilder(ua::Client);
+pub struct ClientBuilder {
+    client: ua::Client,
+    log_buffer: Option<Arc<LogBuffer>>,
+}
 
 impl ClientBuilder {
+    /// Installs the given [`LoggerConfig`], replacing the default one.
+    ///
+    /// This controls which `open62541` messages are forwarded to the `log` crate. By default, all
+    /// messages are forwarded regardless of level or category.
+    #[must_use]
+    pub fn with_logger_config(mut self, logger_config: LoggerConfig) -> Self {
+        let config = client_config(&mut self.client);
+
+        install_logger(config, logger_config);
+        self.log_buffer = None;
+
+        self
+    }
+
+    /// Captures log messages into a bounded in-memory ring buffer instead of forwarding them to
+    /// the `log` crate.
+    ///
+    /// This replaces any previously installed [`LoggerConfig`]. Once connected, use
+    /// [`Client::drain_logs()`]/[`Client::take_log_buffer()`] to pull the accumulated records and
+    /// [`Client::clear_logs()`] to discard them without reading. Useful when embedding
+    /// `open62541` where stdout/stderr are not available and the host wants to poll diagnostic
+    /// output instead.
+    #[must_use]
+    pub fn with_log_buffer(mut self, capacity: usize) -> Self {
+        let config = client_config(&mut self.client);
+
+        self.log_buffer = Some(install_log_buffer(config, capacity));
+
+        self
+    }
+
+    /// Uses anonymous authentication when connecting.
+    ///
+    /// This is the default, so calling this method is only useful to replace the identity token
+    /// installed by an earlier call to [`with_username_password()`](Self::with_username_password)
+    /// or [`with_certificate()`](Self::with_certificate) on the same builder. The encryption set
+    /// up by `with_certificate()` stays in place.
+    #[must_use]
+    pub fn with_anonymous(mut self) -> Self {
+        let config = client_config(&mut self.client);
+
+        set_anonymous_identity_token(config);
+
+        self
+    }
+
+    /// Authenticates with username and password instead of connecting anonymously.
+    ///
+    /// # Panics
+    ///
+    /// The username must be a valid C string, i.e. it must not contain any NUL bytes.
+    #[must_use]
+    pub fn with_username_password(mut self, username: &str, password: &str) -> Self {
+        let config = client_config(&mut self.client);
+
+        set_username_identity_token(config, username, password);
+
+        self
+    }
+
+    /// Authenticates with an X.509 certificate instead of connecting anonymously.
+    ///
+    /// Both `certificate_der` and `private_key_der` must be given in DER encoding. This also
+    /// enables the encryption layer with the given certificate, as required to authenticate via
+    /// an identity token of this kind. Use [`with_security_mode()`](Self::with_security_mode) and
+    /// [`with_security_policy()`](Self::with_security_policy) to select how the resulting
+    /// connection is secured.
+    #[must_use]
+    pub fn with_certificate(mut self, certificate_der: &[u8], private_key_der: &[u8]) -> Self {
+        let config = client_config(&mut self.client);
+
+        // `UA_ClientConfig_setDefaultEncryption()` reinitializes the configuration and would
+        // silently overwrite our installed logger without invoking its `clear()` callback first
+        // (leaking whatever state it owns and reverting to open62541's default stdout logger).
+        // Save and restore it around the call.
+        let logger = config.logger;
+
+        // `setDefaultEncryption()` deep-copies the certificate and private key, so it does not
+        // take ownership of our local copies: free them after the call.
+        let mut certificate = ua::ByteString::new(certificate_der).into_raw();
+        let mut private_key = ua::ByteString::new(private_key_der).into_raw();
+
+        let result = unsafe {
+            UA_ClientConfig_setDefaultEncryption(
+                config,
+                certificate,
+                private_key,
+                ptr::null(),
+                0,
+                ptr::null(),
+                0,
+            )
+        };
+        unsafe {
+            UA_ByteString_clear(&mut certificate);
+            UA_ByteString_clear(&mut private_key);
+        }
+        assert!(result == UA_STATUSCODE_GOOD, "encryption should be set up");
+
+        config.logger = logger;
+
+        set_certificate_identity_token(config, certificate_der);
+
+        self
+    }
+
+    /// Sets the security policy used to secure the connection.
+    ///
+    /// This only takes effect together with a non-default
+    /// [`SecurityMode`](Self::with_security_mode); by default, connections are neither signed nor
+    /// encrypted.
+    ///
+    /// Call this *after* [`with_certificate()`](Self::with_certificate), not before:
+    /// `with_certificate()` calls `UA_ClientConfig_setDefaultEncryption()`, which reinitializes
+    /// `securityPolicyUri` to its own default and would silently discard a policy set here first.
+    #[must_use]
+    pub fn with_security_policy(mut self, security_policy: SecurityPolicy) -> Self {
+        let config = client_config(&mut self.client);
+
+        // Free the previous URI (set by `UA_ClientConfig_setDefault()` or an earlier call to this
+        // method) before overwriting it, to avoid leaking it.
+        unsafe { UA_String_clear(&mut config.securityPolicyUri) };
+
+        config.securityPolicyUri = ua::String::new(security_policy.uri())
+            .expect("security policy URI does not contain NUL bytes")
+            .into_raw();
+
+        self
+    }
+
+    /// Sets the message security mode used to secure the connection.
+    ///
+    /// See [`SecurityMode`] for the available modes.
+    #[must_use]
+    pub fn with_security_mode(mut self, security_mode: SecurityMode) -> Self {
+        let config = client_config(&mut self.client);
+
+        config.securityMode = security_mode.as_inner();
+
+        self
+    }
+
     /// Connects to OPC UA endpoint and returns [`Client`].
     ///
     /// # Errors
     ///
-    /// This fails when the target server is not reachable.
+    /// This fails when the target server is not reachable, or when the server rejects the
+    /// configured credentials (for example `BadUserAccessDenied` or `BadIdentityTokenInvalid`).
     ///
     /// # Panics
     ///
@@ -37,12 +247,15 @@ impl ClientBuilder {
         let endpoint_url =
             CString::new(endpoint_url).expect("endpoint URL does not contain NUL bytes");
 
-        let result = unsafe { UA_Client_connect(self.0.as_mut_ptr(), endpoint_url.as_ptr()) };
+        let result = unsafe { UA_Client_connect(self.client.as_mut_ptr(), endpoint_url.as_ptr()) };
         if result != UA_STATUSCODE_GOOD {
             return Err(Error::new(result));
         }
 
-        Ok(Client(self.0))
+        Ok(Client {
+            client: self.client,
+            log_buffer: self.log_buffer,
+        })
     }
 }
 
@@ -65,7 +278,10 @@ impl Default for ClientBuilder {
         };
         assert!(result == UA_STATUSCODE_GOOD);
 
-        Self(inner)
+        Self {
+            client: inner,
+            log_buffer: None,
+        }
     }
 }
 
@@ -79,7 +295,10 @@ impl Default for ClientBuilder {
 ///
 /// If the connection fails unrecoverably, the client is no longer usable. In this case create a new
 /// client if required.
-pub struct Client(ua::Client);
+pub struct Client {
+    client: ua::Client,
+    log_buffer: Option<Arc<LogBuffer>>,
+}
 
 impl Client {
     /// Creates client connected to endpoint.
@@ -98,59 +317,187 @@ impl Client {
         ClientBuilder::default().connect(endpoint_url)
     }
 
+    /// Returns and clears the log records accumulated in the buffer installed by
+    /// [`ClientBuilder::with_log_buffer()`].
+    ///
+    /// Returns an empty `Vec` if no log buffer was installed.
+    pub fn take_log_buffer(&self) -> Vec<LogRecord> {
+        self.drain_logs()
+    }
+
+    /// Returns and clears the log records accumulated in the buffer installed by
+    /// [`ClientBuilder::with_log_buffer()`].
+    ///
+    /// Returns an empty `Vec` if no log buffer was installed.
+    pub fn drain_logs(&self) -> Vec<LogRecord> {
+        self.log_buffer
+            .as_deref()
+            .map(LogBuffer::drain)
+            .unwrap_or_default()
+    }
+
+    /// Discards the log records accumulated in the buffer installed by
+    /// [`ClientBuilder::with_log_buffer()`], without returning them.
+    ///
+    /// Does nothing if no log buffer was installed.
+    pub fn clear_logs(&self) {
+        if let Some(log_buffer) = &self.log_buffer {
+            log_buffer.clear();
+        }
+    }
+
     /// Turns client into [`AsyncClient`].
     ///
     /// The [`AsyncClient`] can be used to access methods in an asynchronous way.
     #[must_use]
     #[cfg(feature = "tokio")]
     pub fn into_async(self) -> AsyncClient {
-        AsyncClient::from_sync(self.0)
+        AsyncClient::from_sync(self.client)
     }
 }
 
-/// Installs logger that forwards to `log` crate.
+/// Returns mutable reference to the client's configuration.
+fn client_config(client: &mut ua::Client) -> &mut UA_ClientConfig {
+    unsafe { UA_Client_getConfig(client.as_mut_ptr()).as_mut() }
+        .expect("client config should be set")
+}
+
+/// Installs anonymous identity token in client configuration.
+fn set_anonymous_identity_token(config: &mut UA_ClientConfig) {
+    let mut token: UA_AnonymousIdentityToken = unsafe { std::mem::zeroed() };
+
+    unsafe {
+        UA_ExtensionObject_clear(&mut config.userIdentityToken);
+        UA_ExtensionObject_setValueCopy(
+            &mut config.userIdentityToken,
+            ptr::addr_of_mut!(token).cast(),
+            &UA_TYPES[UA_TYPES_ANONYMOUSIDENTITYTOKEN as usize],
+        );
+    }
+}
+
+/// Installs username/password identity token in client configuration.
 ///
-/// This remove an existing logger from the given configuration (by calling its `clear()` callback),
-/// then installs a custom logger that forwards all messages to the corresponding calls in the `log`
-/// crate.
+/// # Panics
 ///
-/// We can use this to prevent `open62541` from installing its own default logger (which outputs any
-/// logs to stdout/stderr directly).
+/// The username must be a valid C string, i.e. it must not contain any NUL bytes.
+fn set_username_identity_token(config: &mut UA_ClientConfig, username: &str, password: &str) {
+    let mut token: UA_UserNameIdentityToken = unsafe { std::mem::zeroed() };
+    // Leave `policyId` empty so the server falls back to the (only) user token policy it has
+    // configured for username/password authentication.
+    token.userName = ua::String::new(username)
+        .expect("username does not contain NUL bytes")
+        .into_raw();
+    token.password = ua::ByteString::new(password.as_bytes()).into_raw();
+
+    unsafe {
+        UA_ExtensionObject_clear(&mut config.userIdentityToken);
+        UA_ExtensionObject_setValueCopy(
+            &mut config.userIdentityToken,
+            ptr::addr_of_mut!(token).cast(),
+            &UA_TYPES[UA_TYPES_USERNAMEIDENTITYTOKEN as usize],
+        );
+        // `setValueCopy()` deep-copies `token`, so the heap allocations in our local copy (the
+        // `userName` and `password` strings) are now duplicated, not moved. Free the local copy.
+        UA_UserNameIdentityToken_clear(&mut token);
+    }
+}
+
+/// Installs X.509 identity token in client configuration.
+fn set_certificate_identity_token(config: &mut UA_ClientConfig, certificate_der: &[u8]) {
+    let mut token: UA_X509IdentityToken = unsafe { std::mem::zeroed() };
+    // Leave `policyId` empty, see `set_username_identity_token()` above.
+    token.certificateData = ua::ByteString::new(certificate_der).into_raw();
+
+    unsafe {
+        UA_ExtensionObject_clear(&mut config.userIdentityToken);
+        UA_ExtensionObject_setValueCopy(
+            &mut config.userIdentityToken,
+            ptr::addr_of_mut!(token).cast(),
+            &UA_TYPES[UA_TYPES_X509IDENTITYTOKEN as usize],
+        );
+        // `setValueCopy()` deep-copies `token`, so the heap allocation in our local copy (the
+        // `certificateData` byte string) is now duplicated, not moved. Free the local copy.
+        UA_X509IdentityToken_clear(&mut token);
+    }
+}
+
+/// Maps `open62541` log level to the corresponding `log` crate level.
+pub(crate) const fn map_log_level(level: UA_LogLevel) -> Option<log::Level> {
+    if level == UA_LogLevel_UA_LOGLEVEL_FATAL {
+        // There is no fatal level in `log`, use `error`.
+        Some(log::Level::Error)
+    } else if level == UA_LogLevel_UA_LOGLEVEL_ERROR {
+        Some(log::Level::Error)
+    } else if level == UA_LogLevel_UA_LOGLEVEL_WARNING {
+        Some(log::Level::Warn)
+    } else if level == UA_LogLevel_UA_LOGLEVEL_INFO {
+        Some(log::Level::Info)
+    } else if level == UA_LogLevel_UA_LOGLEVEL_DEBUG {
+        Some(log::Level::Debug)
+    } else if level == UA_LogLevel_UA_LOGLEVEL_TRACE {
+        Some(log::Level::Trace)
+    } else {
+        // TODO: Handle unexpected level.
+        None
+    }
+}
+
+/// Installs logger that forwards to `log` crate, using the default [`LoggerConfig`].
+///
+/// See [`install_logger()`] for details.
 fn set_default_logger(config: &mut UA_ClientConfig) {
+    install_logger(config, LoggerConfig::default());
+}
+
+/// Installs logger that forwards to `log` crate, filtered by the given [`LoggerConfig`].
+///
+/// This removes an existing logger from the given configuration (by calling its `clear()`
+/// callback), then installs a custom logger that forwards messages allowed by `logger_config` to
+/// the corresponding calls in the `log` crate. Messages below the configured threshold, or from a
+/// muted category, are dropped in the trampoline below before they are even formatted.
+///
+/// We can use this to prevent `open62541` from installing its own default logger (which outputs
+/// any logs to stdout/stderr directly).
+fn install_logger(config: &mut UA_ClientConfig, logger_config: LoggerConfig) {
     unsafe extern "C" fn log_c(
-        _log_context: *mut c_void,
+        log_context: *mut c_void,
         level: UA_LogLevel,
-        _category: UA_LogCategory,
+        category: UA_LogCategory,
         msg: *const c_char,
         _args: va_list,
     ) {
+        let Some(level) = map_log_level(level) else {
+            return;
+        };
+
+        // SAFETY: `log_context` was set to a `Box<LoggerConfig>` below and is only ever accessed
+        // through this trampoline while the logger is installed.
+        let logger_config = unsafe { &*log_context.cast::<LoggerConfig>() };
+        if !logger_config.allows(level, ua::LogCategory::from_raw(category)) {
+            return;
+        }
+
         let msg = unsafe { CStr::from_ptr(msg) }.to_string_lossy();
+        log::log!(level, "{msg}");
+    }
 
-        if level == UA_LogLevel_UA_LOGLEVEL_FATAL {
-            // There is no fatal level  in `log`, use `error`.
-            error!("{msg}");
-        } else if level == UA_LogLevel_UA_LOGLEVEL_ERROR {
-            error!("{msg}");
-        } else if level == UA_LogLevel_UA_LOGLEVEL_WARNING {
-            warn!("{msg}");
-        } else if level == UA_LogLevel_UA_LOGLEVEL_INFO {
-            info!("{msg}");
-        } else if level == UA_LogLevel_UA_LOGLEVEL_DEBUG {
-            debug!("{msg}");
-        } else if level == UA_LogLevel_UA_LOGLEVEL_TRACE {
-            trace!("{msg}");
-        } else {
-            // TODO: Handle unexpected level.
+    unsafe extern "C" fn clear_c(context: *mut c_void) {
+        if !context.is_null() {
+            // SAFETY: This is the same pointer we received from `Box::into_raw()` below, and
+            // `clear_c` runs at most once for it (either here or when the logger is replaced).
+            drop(unsafe { Box::from_raw(context.cast::<LoggerConfig>()) });
         }
     }
 
-    // Reset existing logger configuration.
+    // Reset existing logger configuration. This also frees the `LoggerConfig` installed by an
+    // earlier call to this function, if any.
     if let Some(clear) = config.logger.clear {
         unsafe { clear(config.logger.context) };
     }
 
     // Set logger configuration to our own.
-    config.logger.clear = None;
+    config.logger.clear = Some(clear_c);
     config.logger.log = Some(log_c);
-    config.logger.context = ptr::null_mut();
+    config.logger.context = Box::into_raw(Box::new(logger_config)).cast();
 }
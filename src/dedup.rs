@@ -0,0 +1,92 @@
+use std::ptr;
+
+use open62541_sys::{
+    UA_TYPES, UA_TYPES_VARIANT, UA_Variant, UA_calcSizeBinary, UA_encodeBinary,
+    UA_STATUSCODE_GOOD,
+};
+use xxhash_rust::xxh3::xxh3_64;
+
+use crate::{ua, DataSourceReadContext};
+
+/// Value-change deduplication for a single [`DataSource`](crate::DataSource) node.
+///
+/// Store one `Deduplicator` per node as a field of your [`DataSource`](crate::DataSource)
+/// implementation (the same way this crate's own `examples/cpu_temperature_data_source.rs` keeps
+/// its own per-node state), then call [`set_variant_if_changed()`](Self::set_variant_if_changed)
+/// from [`DataSource::read()`](crate::DataSource::read) instead of `context.set_variant()`
+/// directly.
+///
+/// This diverges from a `DataSourceReadContext::set_variant_if_changed()` method on the context
+/// itself: a standalone type lets each node own independent dedup state without the context
+/// needing to know about deduplication at all, at the cost of callers having to hold a
+/// `Deduplicator` alongside their `DataSource`.
+#[derive(Debug, Default)]
+pub struct Deduplicator {
+    last_hash: Option<u64>,
+}
+
+impl Deduplicator {
+    /// Creates a deduplicator with no prior value, so the next call to
+    /// [`set_variant_if_changed()`](Self::set_variant_if_changed) always sets the value.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets `variant` on the context, unless it is equal to the value last set through this
+    /// deduplicator.
+    ///
+    /// Equality is determined by comparing xxh3 hashes over the binary encoding of the value, not
+    /// by comparing the decoded values themselves. Returns whether the value actually changed
+    /// (and was set); when it did not, [`DataSource::read()`](crate::DataSource::read)
+    /// implementations can use the return value to skip the server-internal update and the
+    /// notifications it would otherwise trigger.
+    pub fn set_variant_if_changed(
+        &mut self,
+        context: &mut DataSourceReadContext,
+        variant: ua::Variant,
+    ) -> bool {
+        // Treat an encode failure as "changed": we have no reliable hash to compare against, and
+        // silently suppressing the update could hide a real value change behind a bad buffer.
+        let hash = hash_variant(variant.inner());
+
+        if hash.is_some() && hash == self.last_hash {
+            return false;
+        }
+        self.last_hash = hash;
+
+        context.set_variant(variant);
+        true
+    }
+}
+
+/// Computes an xxh3 hash over the binary encoding of `variant`, as `open62541` would send it over
+/// the wire, or `None` if encoding it failed.
+fn hash_variant(variant: &UA_Variant) -> Option<u64> {
+    let data_type = &UA_TYPES[UA_TYPES_VARIANT as usize];
+
+    // SAFETY: `variant` is a valid, initialized `UA_Variant`.
+    let size = unsafe { UA_calcSizeBinary(ptr::from_ref(variant).cast(), data_type) };
+
+    let mut buffer = vec![0u8; size];
+    let mut pos = buffer.as_mut_ptr();
+    // SAFETY: `end` points one past the last byte of `buffer`, which outlives this call.
+    let end = unsafe { pos.add(buffer.len()) };
+    // SAFETY: `pos`/`end` delimit `buffer`, which has room for `size` bytes as computed above.
+    let status = unsafe {
+        UA_encodeBinary(
+            ptr::from_ref(variant).cast(),
+            data_type,
+            &mut pos,
+            &end,
+            None,
+            ptr::null_mut(),
+        )
+    };
+
+    if status != UA_STATUSCODE_GOOD {
+        return None;
+    }
+
+    Some(xxh3_64(&buffer))
+}
@@ -0,0 +1,124 @@
+use std::{
+    collections::VecDeque,
+    ffi::{c_char, c_void, CStr},
+    sync::{Arc, Mutex},
+    time::SystemTime,
+};
+
+use open62541_sys::{va_list, UA_ClientConfig, UA_LogCategory, UA_LogLevel};
+
+use crate::{client::map_log_level, ua};
+
+/// A single log line captured by [`LogBuffer`].
+#[derive(Debug, Clone)]
+pub struct LogRecord {
+    pub level: log::Level,
+    pub category: ua::LogCategory,
+    pub timestamp: SystemTime,
+    pub message: String,
+}
+
+/// Fixed-capacity ring buffer that captures log messages instead of forwarding them to the `log`
+/// crate.
+///
+/// Each message is stored as the format string `open62541` passes to the logger; its printf-style
+/// arguments are not substituted.
+///
+/// Install with [`ClientBuilder::with_log_buffer()`](crate::ClientBuilder::with_log_buffer), then
+/// use [`Client::drain_logs()`](crate::Client::drain_logs) /
+/// [`Client::take_log_buffer()`](crate::Client::take_log_buffer) to pull accumulated records, and
+/// [`Client::clear_logs()`](crate::Client::clear_logs) to discard them without reading. This is
+/// useful when embedding `open62541` clients where stdout is not available and the host wants to
+/// poll diagnostic output on demand instead.
+#[derive(Debug)]
+pub(crate) struct LogBuffer {
+    capacity: usize,
+    records: Mutex<VecDeque<LogRecord>>,
+}
+
+impl LogBuffer {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            records: Mutex::new(VecDeque::with_capacity(capacity)),
+        }
+    }
+
+    fn push(&self, record: LogRecord) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        let mut records = self.records.lock().unwrap_or_else(|err| err.into_inner());
+
+        if records.len() == self.capacity {
+            records.pop_front();
+        }
+        records.push_back(record);
+    }
+
+    /// Returns and clears all records accumulated so far.
+    pub(crate) fn drain(&self) -> Vec<LogRecord> {
+        let mut records = self.records.lock().unwrap_or_else(|err| err.into_inner());
+
+        records.drain(..).collect()
+    }
+
+    /// Discards all records accumulated so far, without returning them.
+    pub(crate) fn clear(&self) {
+        self.records.lock().unwrap_or_else(|err| err.into_inner()).clear();
+    }
+}
+
+/// Installs [`LogBuffer`] as logger backend in the given client configuration.
+///
+/// This removes an existing logger from the given configuration (by calling its `clear()`
+/// callback), then installs a logger that appends log messages (without their printf-style
+/// arguments) into the returned [`LogBuffer`] instead of forwarding them to the `log` crate.
+pub(crate) fn install_log_buffer(config: &mut UA_ClientConfig, capacity: usize) -> Arc<LogBuffer> {
+    unsafe extern "C" fn log_c(
+        log_context: *mut c_void,
+        level: UA_LogLevel,
+        category: UA_LogCategory,
+        msg: *const c_char,
+        _args: va_list,
+    ) {
+        let Some(level) = map_log_level(level) else {
+            return;
+        };
+
+        // SAFETY: `log_context` was set to an `Arc<LogBuffer>` below and is only ever accessed
+        // through this trampoline while the logger is installed.
+        let log_buffer = unsafe { &*log_context.cast::<Arc<LogBuffer>>() };
+
+        let msg = unsafe { CStr::from_ptr(msg) }.to_string_lossy().into_owned();
+
+        log_buffer.push(LogRecord {
+            level,
+            category: ua::LogCategory::from_raw(category),
+            timestamp: SystemTime::now(),
+            message: msg,
+        });
+    }
+
+    unsafe extern "C" fn clear_c(context: *mut c_void) {
+        if !context.is_null() {
+            // SAFETY: This is the same pointer we received from `Box::into_raw()` below, and
+            // `clear_c` runs at most once for it (either here or when the logger is replaced).
+            drop(unsafe { Box::from_raw(context.cast::<Arc<LogBuffer>>()) });
+        }
+    }
+
+    // Reset existing logger configuration.
+    if let Some(clear) = config.logger.clear {
+        unsafe { clear(config.logger.context) };
+    }
+
+    let log_buffer = Arc::new(LogBuffer::new(capacity));
+
+    config.logger.clear = Some(clear_c);
+    config.logger.log = Some(log_c);
+    config.logger.context = Box::into_raw(Box::new(Arc::clone(&log_buffer))).cast();
+
+    log_buffer
+}
@@ -0,0 +1,52 @@
+use std::collections::HashSet;
+
+use crate::ua;
+
+/// Configuration for the logger installed by [`ClientBuilder`](crate::ClientBuilder).
+///
+/// Only the client's logger uses it; the server's logger is not affected.
+///
+/// By default, every message is forwarded to the `log` crate at its corresponding level. Use
+/// [`with_threshold()`](Self::with_threshold) and
+/// [`with_muted_category()`](Self::with_muted_category) to drop messages before they are
+/// formatted and passed on, instead of reconfiguring `open62541` itself.
+#[derive(Debug, Clone)]
+pub struct LoggerConfig {
+    pub(crate) threshold: log::LevelFilter,
+    pub(crate) muted_categories: HashSet<ua::LogCategory>,
+}
+
+impl Default for LoggerConfig {
+    fn default() -> Self {
+        Self {
+            threshold: log::LevelFilter::Trace,
+            muted_categories: HashSet::new(),
+        }
+    }
+}
+
+impl LoggerConfig {
+    /// Sets the minimum level a message must have to be forwarded to the `log` crate.
+    ///
+    /// Messages below this level are dropped before they are formatted.
+    #[must_use]
+    pub fn with_threshold(mut self, threshold: log::LevelFilter) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    /// Mutes the given `open62541` log category, e.g. [`LogCategory::Network`](ua::LogCategory::Network).
+    ///
+    /// Messages from muted categories are dropped regardless of their level. Call this repeatedly
+    /// to mute several categories.
+    #[must_use]
+    pub fn with_muted_category(mut self, category: ua::LogCategory) -> Self {
+        self.muted_categories.insert(category);
+        self
+    }
+
+    /// Checks whether a message at the given level and category should be forwarded.
+    pub(crate) fn allows(&self, level: log::Level, category: ua::LogCategory) -> bool {
+        level <= self.threshold && !self.muted_categories.contains(&category)
+    }
+}
@@ -0,0 +1,156 @@
+use std::{ffi::c_void, marker::PhantomData};
+
+use open62541_sys::{
+    UA_Server, UA_Server_addRepeatedCallback, UA_Server_readValue,
+    UA_Server_removeRepeatedCallback, UA_Server_writeValue, UA_UInt64, UA_Variant,
+    UA_Variant_clear, UA_STATUSCODE_GOOD,
+};
+
+use crate::{ua, Error, Server};
+
+/// Configuration for periodically sampling a variable.
+///
+/// Pass this to [`Server::add_sampled_data_source()`] to force a variable backed by a
+/// [`DataSource`](crate::DataSource) or [`ua::DataSource`] to be re-read and its result written
+/// back at a fixed interval, rather than only on demand when a client reads it. This is useful
+/// e.g. to drive subscription change notifications for data sources that change independently of
+/// client reads.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+pub struct SamplingConfig {
+    /// Interval between samples, in milliseconds.
+    pub interval_ms: f64,
+}
+
+/// Handle to a sampling job started by [`Server::add_sampled_data_source()`].
+///
+/// Dropping this handle does not stop sampling, call [`stop()`](Self::stop) explicitly. The
+/// handle borrows the [`Server`] it was created from, so it cannot outlive it, which rules out
+/// calling [`stop()`](Self::stop) on a server that has already been torn down.
+#[must_use = "dropping `SamplingHandle` does not stop sampling, call `stop()` explicitly"]
+pub struct SamplingHandle<'server> {
+    server: *mut UA_Server,
+    callback_id: UA_UInt64,
+    context: *mut SamplingContext,
+    _server: PhantomData<&'server Server>,
+}
+
+// SAFETY: `SamplingHandle` only ever touches `server`/`context` through `stop()`, which is the
+// only method taking `self` by value (so it cannot run concurrently with itself), and
+// `UA_Server_removeRepeatedCallback()` is safe to call from any thread while the server is
+// running.
+unsafe impl Send for SamplingHandle<'_> {}
+
+impl SamplingHandle<'_> {
+    /// Stops sampling and releases the resources associated with it.
+    pub fn stop(self) {
+        unsafe { UA_Server_removeRepeatedCallback(self.server, self.callback_id) };
+
+        // SAFETY: This is the same pointer we received from `Box::into_raw()` in
+        // `add_sampled_data_source()`, and we are the only owner of it (the repeated callback has
+        // just been removed above, so it cannot race with this).
+        drop(unsafe { Box::from_raw(self.context) });
+    }
+}
+
+/// Context passed to the repeated callback below, owned by the server for as long as sampling
+/// is active (i.e. until [`SamplingHandle::stop()`] is called).
+struct SamplingContext {
+    node_id: ua::NodeId,
+}
+
+impl Server {
+    /// Periodically re-reads the variable at `node_id` and writes the result back into it, every
+    /// `config.interval_ms` milliseconds.
+    ///
+    /// This drives the sample through the server's own node dispatch
+    /// (`UA_Server_readValue()`/`UA_Server_writeValue()`), the same paths a client's read/write
+    /// request would take, so it works for a variable backed by either the high-level
+    /// [`DataSource`](crate::DataSource) trait or the low-level [`ua::DataSource`] -- `node_id`
+    /// must already be registered as one of these before calling this function.
+    ///
+    /// The write-back is what triggers subscription change notifications; it only succeeds if
+    /// the node's `write()` accepts it. A read-only data source, like the `ControllerDataSource`
+    /// in this crate's `examples/cpu_temperature_data_source.rs` (whose `write()` always returns
+    /// [`DataSourceError::NotSupported`](crate::DataSourceError::NotSupported)), will have its
+    /// write-back rejected on every tick; each rejection is logged via the `log` crate at `warn`
+    /// level rather than silently dropped, but no notification fires. Use a data source whose
+    /// `write()` accepts the value it was just asked to read if you need this to actually publish
+    /// samples.
+    ///
+    /// Use the returned [`SamplingHandle`] to stop sampling again.
+    ///
+    /// # Errors
+    ///
+    /// This fails when the repeated callback cannot be registered with the server.
+    pub fn add_sampled_data_source(
+        &self,
+        node_id: &ua::NodeId,
+        config: SamplingConfig,
+    ) -> Result<SamplingHandle<'_>, Error> {
+        unsafe extern "C" fn callback_c(server: *mut UA_Server, data: *mut c_void) {
+            // SAFETY: `data` was set to a `Box<SamplingContext>` below and is only ever accessed
+            // through this trampoline while sampling is active.
+            let context = unsafe { &mut *data.cast::<SamplingContext>() };
+
+            let mut value: UA_Variant = unsafe { std::mem::zeroed() };
+            // SAFETY: `context.node_id` is valid for the lifetime of this call, and `value` is a
+            // valid, zeroed output buffer.
+            let status =
+                unsafe { UA_Server_readValue(server, *context.node_id.inner(), &mut value) };
+
+            if status == UA_STATUSCODE_GOOD {
+                // `UA_Server_writeValue()` copies both arguments internally and does not take
+                // ownership of either, so passing our node ID by value here is safe even though
+                // we keep using it on the next tick.
+                let write_status =
+                    unsafe { UA_Server_writeValue(server, *context.node_id.inner(), value) };
+                if write_status != UA_STATUSCODE_GOOD {
+                    // E.g. a read-only `DataSource` rejects every write-back with
+                    // `UA_STATUSCODE_BADNOTWRITABLE`: surface that instead of discarding it, since
+                    // it means this sample never reaches subscribers.
+                    log::warn!(
+                        "sampling {:?}: write-back rejected with status {write_status:#010x}",
+                        context.node_id,
+                    );
+                }
+            } else {
+                log::warn!(
+                    "sampling {:?}: read failed with status {status:#010x}",
+                    context.node_id,
+                );
+            }
+
+            // We own `value` regardless of whether the write above happened: free it now.
+            unsafe { UA_Variant_clear(&mut value) };
+        }
+
+        let context = Box::into_raw(Box::new(SamplingContext {
+            node_id: node_id.clone(),
+        }));
+
+        let mut callback_id: UA_UInt64 = 0;
+        let server_ptr = self.as_ptr();
+        let result = unsafe {
+            UA_Server_addRepeatedCallback(
+                server_ptr,
+                Some(callback_c),
+                context.cast(),
+                config.interval_ms,
+                &mut callback_id,
+            )
+        };
+        if result != UA_STATUSCODE_GOOD {
+            // Registration failed, nothing owns `context` yet: reclaim and drop it ourselves.
+            drop(unsafe { Box::from_raw(context) });
+            return Err(Error::new(result));
+        }
+
+        Ok(SamplingHandle {
+            server: server_ptr,
+            callback_id,
+            context,
+            _server: PhantomData,
+        })
+    }
+}
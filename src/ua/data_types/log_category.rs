@@ -0,0 +1,54 @@
+use open62541_sys::{
+    UA_LogCategory, UA_LOGCATEGORY_CLIENT, UA_LOGCATEGORY_EVENTLOOP, UA_LOGCATEGORY_NETWORK,
+    UA_LOGCATEGORY_SECURECHANNEL, UA_LOGCATEGORY_SECURITYPOLICY, UA_LOGCATEGORY_SERVER,
+    UA_LOGCATEGORY_SESSION, UA_LOGCATEGORY_USERLAND,
+};
+
+/// Category of a log message.
+///
+/// Safe wrapper for `open62541`'s `UA_LogCategory`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LogCategory {
+    Network,
+    SecureChannel,
+    Session,
+    Server,
+    Client,
+    Userland,
+    SecurityPolicy,
+    EventLoop,
+    /// A category value not recognized by this crate, kept as-is.
+    Other(UA_LogCategory),
+}
+
+impl LogCategory {
+    #[must_use]
+    pub(crate) fn from_raw(category: UA_LogCategory) -> Self {
+        match category {
+            c if c == UA_LOGCATEGORY_NETWORK => Self::Network,
+            c if c == UA_LOGCATEGORY_SECURECHANNEL => Self::SecureChannel,
+            c if c == UA_LOGCATEGORY_SESSION => Self::Session,
+            c if c == UA_LOGCATEGORY_SERVER => Self::Server,
+            c if c == UA_LOGCATEGORY_CLIENT => Self::Client,
+            c if c == UA_LOGCATEGORY_USERLAND => Self::Userland,
+            c if c == UA_LOGCATEGORY_SECURITYPOLICY => Self::SecurityPolicy,
+            c if c == UA_LOGCATEGORY_EVENTLOOP => Self::EventLoop,
+            other => Self::Other(other),
+        }
+    }
+
+    #[must_use]
+    pub(crate) const fn as_raw(self) -> UA_LogCategory {
+        match self {
+            Self::Network => UA_LOGCATEGORY_NETWORK,
+            Self::SecureChannel => UA_LOGCATEGORY_SECURECHANNEL,
+            Self::Session => UA_LOGCATEGORY_SESSION,
+            Self::Server => UA_LOGCATEGORY_SERVER,
+            Self::Client => UA_LOGCATEGORY_CLIENT,
+            Self::Userland => UA_LOGCATEGORY_USERLAND,
+            Self::SecurityPolicy => UA_LOGCATEGORY_SECURITYPOLICY,
+            Self::EventLoop => UA_LOGCATEGORY_EVENTLOOP,
+            Self::Other(raw) => raw,
+        }
+    }
+}